@@ -1,277 +1,890 @@
-use std::io::{stdout, Write};
-use std::time::{Instant, Duration};
-
-// Canvas dimensions and constants
-const WIDTH: usize = 160;
-const HEIGHT: usize = 80;
-const FOCAL_LENGTH: f32 = 100.0;  // Reduced for a wider field of view
-const CAMERA_DISTANCE: f32 = 10.0;  // Reduced to bring camera closer
-const BASE_SPEED: f32 = 0.005;
-const TARGET_FPS: u64 = 60;
-const ORBIT_SPEED: f32 = 0.02;
-const ORBIT_A: f32 = 6.0;  // Reduced orbit radius to fit closer view
-const ORBIT_B: f32 = 3.0;
-const ORBIT_C: f32 = 2.0;
-const SPHERE_RADIUS: f32 = 2.0;  // Slightly smaller sphere for closer view
-
-// Define vertices and edges for all five Platonic solids
-const TETRAHEDRON_VERTS: [[f32; 3]; 4] = [
-    [1.0, 1.0, 1.0], [-1.0, -1.0, 1.0], [-1.0, 1.0, -1.0], [1.0, -1.0, -1.0],
-];
-const TETRAHEDRON_EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
-
-const CUBE_VERTS: [[f32; 3]; 8] = [
-    [-1.0, -1.0, -1.0], [1.0, -1.0, -1.0], [1.0, 1.0, -1.0], [-1.0, 1.0, -1.0],
-    [-1.0, -1.0, 1.0], [1.0, -1.0, 1.0], [1.0, 1.0, 1.0], [-1.0, 1.0, 1.0],
-];
-const CUBE_EDGES: [(usize, usize); 12] = [
-    (0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6), (6, 7), (7, 4),
-    (0, 4), (1, 5), (2, 6), (3, 7),
-];
-
-const OCTAHEDRON_VERTS: [[f32; 3]; 6] = [
-    [1.0, 0.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, -1.0, 0.0],
-    [0.0, 0.0, 1.0], [0.0, 0.0, -1.0],
-];
-const OCTAHEDRON_EDGES: [(usize, usize); 12] = [
-    (0, 2), (0, 3), (0, 4), (0, 5), (1, 2), (1, 3), (1, 4), (1, 5),
-    (2, 4), (2, 5), (3, 4), (3, 5),
-];
-
-const DODECAHEDRON_VERTS: [[f32; 3]; 20] = [
-    [1.0, 1.0, 1.0], [1.0, 1.0, -1.0], [1.0, -1.0, 1.0], [1.0, -1.0, -1.0],
-    [-1.0, 1.0, 1.0], [-1.0, 1.0, -1.0], [-1.0, -1.0, 1.0], [-1.0, -1.0, -1.0],
-    [0.0, 1.618, 0.618], [0.0, 1.618, -0.618], [0.0, -1.618, 0.618], [0.0, -1.618, -0.618],
-    [0.618, 0.0, 1.618], [0.618, 0.0, -1.618], [-0.618, 0.0, 1.618], [-0.618, 0.0, -1.618],
-    [1.618, 0.618, 0.0], [1.618, -0.618, 0.0], [-1.618, 0.618, 0.0], [-1.618, -0.618, 0.0],
-];
-const DODECAHEDRON_EDGES: [(usize, usize); 30] = [
-    (0, 12), (0, 16), (0, 8), (1, 13), (1, 16), (1, 9),
-    (2, 12), (2, 17), (2, 10), (3, 13), (3, 17), (3, 11),
-    (4, 14), (4, 18), (4, 8), (5, 15), (5, 18), (5, 9),
-    (6, 14), (6, 19), (6, 10), (7, 15), (7, 19), (7, 11),
-    (8, 9), (10, 11), (12, 14), (13, 15), (16, 17), (18, 19),
-];
-
-const ICOSAHEDRON_VERTS: [[f32; 3]; 12] = [
-    [0.0, 1.0, 1.618], [0.0, 1.0, -1.618], [0.0, -1.0, 1.618], [0.0, -1.0, -1.618],
-    [1.618, 0.0, 1.0], [1.618, 0.0, -1.0], [-1.618, 0.0, 1.0], [-1.618, 0.0, -1.0],
-    [1.0, 1.618, 0.0], [1.0, -1.618, 0.0], [-1.0, 1.618, 0.0], [-1.0, -1.618, 0.0],
-];
-const ICOSAHEDRON_EDGES: [(usize, usize); 30] = [
-    (0, 4), (0, 6), (0, 8), (0, 10), (0, 2), (1, 5), (1, 7), (1, 8), (1, 10), (1, 3),
-    (2, 4), (2, 6), (2, 9), (2, 11), (3, 5), (3, 7), (3, 9), (3, 11), (4, 5), (4, 8),
-    (4, 9), (5, 8), (5, 9), (6, 7), (6, 10), (6, 11), (7, 10), (7, 11), (8, 10), (9, 11),
-];
-
-// Define a simple wireframe sphere
-const SPHERE_LATS: usize = 10;
-const SPHERE_LONGS: usize = 20;
-
-struct Solid {
-    vertices: Vec<[f32; 3]>,
-    edges: &'static [(usize, usize)],
-    scale: f32,
-    sv_ratio: f32,
-    angle: f32,
-}
-
-impl Solid {
-    fn new(vertices: &'static [[f32; 3]], edges: &'static [(usize, usize)], scale: f32, sv_ratio: f32) -> Self {
-        let scaled_vertices = vertices.iter().map(|&v| [v[0] * scale, v[1] * scale, v[2] * scale]).collect();
-        Solid {
-            vertices: scaled_vertices,
-            edges,
-            scale,
-            sv_ratio,
-            angle: 0.0,
-        }
-    }
-
-    fn update(&mut self, omega: f32) {
-        self.angle += omega;
-    }
-}
-
-fn main() {
-    let scales = [0.234, 0.286, 0.606, 0.539, 1.0];
-    let sv_ratios = [14.697, 6.0, 7.348, 3.013, 4.899];
-
-    let mut solids = vec![
-        Solid::new(&TETRAHEDRON_VERTS, &TETRAHEDRON_EDGES, scales[0], sv_ratios[0]),
-        Solid::new(&CUBE_VERTS, &CUBE_EDGES, scales[1], sv_ratios[1]),
-        Solid::new(&OCTAHEDRON_VERTS, &OCTAHEDRON_EDGES, scales[2], sv_ratios[2]),
-        Solid::new(&DODECAHEDRON_VERTS, &DODECAHEDRON_EDGES, scales[3], sv_ratios[3]),
-        Solid::new(&ICOSAHEDRON_VERTS, &ICOSAHEDRON_EDGES, scales[4], sv_ratios[4]),
-    ];
-
-    let mut screen = vec![vec![' '; WIDTH]; HEIGHT];
-    let mut last_screen = vec![vec![' '; WIDTH]; HEIGHT];
-    let mut depth = vec![vec![f32::MIN; WIDTH]; HEIGHT];
-    let frame_time = Duration::from_millis(1000 / TARGET_FPS);
-    let light_dir = [1.0, 1.0, 1.0];
-
-    // Sphere setup
-    let mut sphere_vertices = Vec::new();
-    for i in 0..SPHERE_LATS {
-        let lat = std::f32::consts::PI * i as f32 / (SPHERE_LATS - 1) as f32 - std::f32::consts::PI / 2.0;
-        for j in 0..SPHERE_LONGS {
-            let lon = 2.0 * std::f32::consts::PI * j as f32 / SPHERE_LONGS as f32;
-            let x = SPHERE_RADIUS * lat.cos() * lon.cos();
-            let y = SPHERE_RADIUS * lat.sin();
-            let z = SPHERE_RADIUS * lat.cos() * lon.sin();
-            sphere_vertices.push([x, y, z]);
-        }
-    }
-    let mut sphere_edges = Vec::new();
-    for i in 0..SPHERE_LATS {
-        for j in 0..SPHERE_LONGS {
-            let idx = i * SPHERE_LONGS + j;
-            if i < SPHERE_LATS - 1 {
-                sphere_edges.push((idx, idx + SPHERE_LONGS));
-            }
-            let next_j = (j + 1) % SPHERE_LONGS;
-            sphere_edges.push((idx, i * SPHERE_LONGS + next_j));
-        }
-    }
-
-    print!("\x1B[2J\x1B[1;1H"); // Clear screen and move to top-left corner
-    stdout().flush().unwrap();
-
-    let mut last_frame = Instant::now();
-    let mut orbit_angle: f32 = 0.0;
-
-    loop {
-        let now = Instant::now();
-        if now.duration_since(last_frame) >= frame_time {
-            depth.iter_mut().for_each(|row| row.fill(f32::MIN));
-            screen.iter_mut().for_each(|row| row.fill(' '));
-
-            // Compute the position of the solids' center in the elliptical orbit
-            let orbit_x = ORBIT_A * orbit_angle.cos();
-            let orbit_y = ORBIT_B * orbit_angle.sin();
-            let orbit_z = ORBIT_C * (orbit_angle + std::f32::consts::PI / 2.0).sin();
-
-            // Render the central sphere
-            let mut sphere_projected = vec![[0; 2]; sphere_vertices.len()];
-            let mut sphere_depths = vec![0.0; sphere_vertices.len()];
-            for (i, vertex) in sphere_vertices.iter().enumerate() {
-                let x = vertex[0];
-                let y = vertex[1];
-                let z = vertex[2];
-                sphere_depths[i] = z;
-                let (px, py) = project_vertex(x, y, z);
-                sphere_projected[i] = [px, py];
-            }
-            for &(v1, v2) in sphere_edges.iter() {
-                let p1 = sphere_projected[v1];
-                let p2 = sphere_projected[v2];
-                let avg_depth = (sphere_depths[v1] + sphere_depths[v2]) / 2.0;
-                let dx = sphere_vertices[v2][0] - sphere_vertices[v1][0];
-                let dy = sphere_vertices[v2][1] - sphere_vertices[v1][1];
-                let dz = sphere_vertices[v2][2] - sphere_vertices[v1][2];
-                let normal = [dx, dy, dz];
-                let light = dot_product(normalize(normal), normalize(light_dir));
-                let intensity = (light * 0.5 + 0.5).max(0.0).min(1.0);
-                draw_line(&mut screen, &mut depth, p1[0], p1[1], p2[0], p2[1], avg_depth, intensity);
-            }
-
-            // Render the nested solids
-            for solid in solids.iter_mut() {
-                let omega = BASE_SPEED * solid.sv_ratio;
-                solid.update(omega);
-                let mut projected = vec![[0; 2]; solid.vertices.len()];
-                let mut depths = vec![0.0; solid.vertices.len()];
-                for (i, vertex) in solid.vertices.iter().enumerate() {
-                    let x = vertex[0] * solid.angle.cos() + vertex[2] * solid.angle.sin();
-                    let y = vertex[1];
-                    let z = -vertex[0] * solid.angle.sin() + vertex[2] * solid.angle.cos();
-                    let orbited_x = x + orbit_x;
-                    let orbited_y = y + orbit_y;
-                    let orbited_z = z + orbit_z;
-                    depths[i] = orbited_z;
-                    let (px, py) = project_vertex(orbited_x, orbited_y, orbited_z);
-                    projected[i] = [px, py];
-                }
-                for &(v1, v2) in solid.edges.iter() {
-                    let p1 = projected[v1];
-                    let p2 = projected[v2];
-                    let avg_depth = (depths[v1] + depths[v2]) / 2.0;
-                    let dx = solid.vertices[v2][0] - solid.vertices[v1][0];
-                    let dy = solid.vertices[v2][1] - solid.vertices[v1][1];
-                    let dz = solid.vertices[v2][2] - solid.vertices[v1][2];
-                    let normal = [dx, dy, dz];
-                    let light = dot_product(normalize(normal), normalize(light_dir));
-                    let intensity = (light * 0.5 + 0.5).max(0.0).min(1.0);
-                    draw_line(&mut screen, &mut depth, p1[0], p1[1], p2[0], p2[1], avg_depth, intensity);
-                }
-            }
-
-            update_screen(&screen, &last_screen);
-            last_screen.clone_from(&screen);
-            stdout().flush().unwrap();
-
-            orbit_angle += ORBIT_SPEED;
-            last_frame = now;
-        }
-        std::thread::sleep(frame_time - now.duration_since(last_frame));
-    }
-}
-
-fn update_screen(screen: &Vec<Vec<char>>, last_screen: &Vec<Vec<char>>) {
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            if screen[y][x] != last_screen[y][x] {
-                print!("\x1B[{};{}H{}", y + 1, x + 1, screen[y][x]);
-            }
-        }
-    }
-}
-
-fn draw_line(screen: &mut Vec<Vec<char>>, depth: &mut Vec<Vec<f32>>, 
-             x0: i32, y0: i32, x1: i32, y1: i32, z: f32, intensity: f32) {
-    let dx = (x1 - x0).abs();
-    let dy = (y1 - y0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx - dy;
-
-    let chars = [' ', '.', ',', ':', ';', '-', '=', '+', '*', '#', '%', '@'];
-    let char_idx = (intensity * (chars.len() - 1) as f32) as usize;
-    let char = chars[char_idx];
-
-    let mut x = x0;
-    let mut y = y0;
-    loop {
-        let wrapped_x = (x + WIDTH as i32) % WIDTH as i32;
-        let wrapped_y = (y + HEIGHT as i32) % HEIGHT as i32;
-        if wrapped_x >= 0 && wrapped_x < WIDTH as i32 && wrapped_y >= 0 && wrapped_y < HEIGHT as i32 {
-            let idx_x = wrapped_x as usize;
-            let idx_y = wrapped_y as usize;
-            if z > depth[idx_y][idx_x] {
-                depth[idx_y][idx_x] = z;
-                screen[idx_y][idx_x] = char;
-            }
-        }
-        if x == x1 && y == y1 { break; }
-        let e2 = 2 * err;
-        if e2 > -dy { err -= dy; x += sx; }
-        if e2 < dx { err += dx; y += sy; }
-    }
-}
-
-fn normalize(v: [f32; 3]) -> [f32; 3] {
-    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
-    if len == 0.0 { [0.0, 0.0, 0.0] } else { [v[0] / len, v[1] / len, v[2] / len] }
-}
-
-fn dot_product(v1: [f32; 3], v2: [f32; 3]) -> f32 {
-    v1[0] * v2[0] + v1[1] * v2[1] + v1[2] * v2[2]
-}
-
-fn project_vertex(x: f32, y: f32, z: f32) -> (i32, i32) {
-    let scale = FOCAL_LENGTH / (z + CAMERA_DISTANCE);
-    let px = (x * scale + (WIDTH as f32 / 2.0)).round() as i32;
-    let py = (y * scale * 0.5 + (HEIGHT as f32 / 2.0)).round() as i32;
-    (px, py)
-}
\ No newline at end of file
+use std::io::{stdout, Write};
+use std::time::{Instant, Duration};
+
+// Canvas dimensions and constants
+const WIDTH: usize = 160;
+const HEIGHT: usize = 80;
+const BASE_SPEED: f32 = 0.005;
+const TARGET_FPS: u64 = 60;
+const ORBIT_SPEED: f32 = 0.02;  // Initial tangential speed scale for each solid's starting orbit
+const ORBIT_A: f32 = 6.0;  // Reduced orbit radius to fit closer view
+const ORBIT_B: f32 = 3.0;
+const ORBIT_C: f32 = 2.0;
+const SPHERE_RADIUS: f32 = 2.0;  // Slightly smaller sphere for closer view
+
+// Rigid-body billiard physics for the orbiting solids
+const RESTITUTION: f32 = 0.9;
+const BOUNDS: [f32; 3] = [9.0, 5.0, 4.0]; // half-extents of the bounce box, centered on the origin
+
+// Camera setup for the Mat4 pipeline
+const FOVY: f32 = 60.0 * std::f32::consts::PI / 180.0;
+const NEAR: f32 = 0.1;
+const FAR: f32 = 100.0;
+const CAMERA_EYE: [f32; 3] = [0.0, 0.0, -16.0];
+const CAMERA_TARGET: [f32; 3] = [0.0, 0.0, 0.0];
+const CAMERA_UP: [f32; 3] = [0.0, 1.0, 0.0];
+// Fixed camera forward direction, used for back-face culling.
+const VIEW_DIR: [f32; 3] = [0.0, 0.0, 1.0];
+
+// Raymarching tunables
+const MARCH_MAX_STEPS: u32 = 128;
+const MARCH_MAX_DIST: f32 = 60.0;
+const MARCH_HIT_EPS: f32 = 1e-3;
+const SHADOW_SOFTNESS: f32 = 16.0;
+const SHADOW_MAX_DIST: f32 = 20.0;
+
+// ASCII intensity ramp shared by the wireframe, raymarch and ray-trace renderers.
+const RAMP: [char; 12] = [' ', '.', ',', ':', ';', '-', '=', '+', '*', '#', '%', '@'];
+
+fn ramp_char(intensity: f32) -> char {
+    let idx = (intensity.max(0.0).min(1.0) * (RAMP.len() - 1) as f32) as usize;
+    RAMP[idx]
+}
+// Terminal character cells are roughly twice as tall as they are wide, so the
+// projection aspect ratio has to account for that to keep orbits circular.
+const CHAR_ASPECT: f32 = WIDTH as f32 / (HEIGHT as f32 * 2.0);
+
+// Define vertices and faces for all five Platonic solids. Each face lists its
+// vertex indices in counter-clockwise winding as seen from outside the solid,
+// so `cross(v1 - v0, v2 - v0)` gives an outward-facing normal.
+const TETRAHEDRON_VERTS: [[f32; 3]; 4] = [
+    [1.0, 1.0, 1.0], [-1.0, -1.0, 1.0], [-1.0, 1.0, -1.0], [1.0, -1.0, -1.0],
+];
+const TETRAHEDRON_FACES: [&[usize]; 4] = [
+    &[1, 2, 3], &[0, 3, 2], &[0, 1, 3], &[0, 2, 1],
+];
+
+const CUBE_VERTS: [[f32; 3]; 8] = [
+    [-1.0, -1.0, -1.0], [1.0, -1.0, -1.0], [1.0, 1.0, -1.0], [-1.0, 1.0, -1.0],
+    [-1.0, -1.0, 1.0], [1.0, -1.0, 1.0], [1.0, 1.0, 1.0], [-1.0, 1.0, 1.0],
+];
+const CUBE_FACES: [&[usize]; 6] = [
+    &[3, 2, 1, 0], &[4, 5, 6, 7], &[0, 1, 5, 4], &[7, 6, 2, 3], &[4, 7, 3, 0], &[1, 2, 6, 5],
+];
+
+const OCTAHEDRON_VERTS: [[f32; 3]; 6] = [
+    [1.0, 0.0, 0.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, -1.0, 0.0],
+    [0.0, 0.0, 1.0], [0.0, 0.0, -1.0],
+];
+const OCTAHEDRON_FACES: [&[usize]; 8] = [
+    &[0, 2, 4], &[0, 4, 3], &[0, 3, 5], &[0, 5, 2],
+    &[1, 4, 2], &[1, 3, 4], &[1, 5, 3], &[1, 2, 5],
+];
+
+const DODECAHEDRON_VERTS: [[f32; 3]; 20] = [
+    [1.0, 1.0, 1.0], [1.0, 1.0, -1.0], [1.0, -1.0, 1.0], [1.0, -1.0, -1.0],
+    [-1.0, 1.0, 1.0], [-1.0, 1.0, -1.0], [-1.0, -1.0, 1.0], [-1.0, -1.0, -1.0],
+    [0.0, 1.618, 0.618], [0.0, 1.618, -0.618], [0.0, -1.618, 0.618], [0.0, -1.618, -0.618],
+    [0.618, 0.0, 1.618], [0.618, 0.0, -1.618], [-0.618, 0.0, 1.618], [-0.618, 0.0, -1.618],
+    [1.618, 0.618, 0.0], [1.618, -0.618, 0.0], [-1.618, 0.618, 0.0], [-1.618, -0.618, 0.0],
+];
+const DODECAHEDRON_FACES: [&[usize]; 12] = [
+    &[0, 8, 4, 14, 12], &[0, 12, 2, 17, 16], &[0, 16, 1, 9, 8],
+    &[8, 9, 5, 18, 4], &[12, 14, 6, 10, 2], &[16, 17, 3, 13, 1],
+    &[9, 1, 13, 15, 5], &[14, 4, 18, 19, 6], &[17, 2, 10, 11, 3],
+    &[18, 5, 15, 7, 19], &[13, 3, 11, 7, 15], &[10, 6, 19, 7, 11],
+];
+
+const ICOSAHEDRON_VERTS: [[f32; 3]; 12] = [
+    [0.0, 1.0, 1.618], [0.0, 1.0, -1.618], [0.0, -1.0, 1.618], [0.0, -1.0, -1.618],
+    [1.618, 0.0, 1.0], [1.618, 0.0, -1.0], [-1.618, 0.0, 1.0], [-1.618, 0.0, -1.0],
+    [1.0, 1.618, 0.0], [1.0, -1.618, 0.0], [-1.0, 1.618, 0.0], [-1.0, -1.618, 0.0],
+];
+const ICOSAHEDRON_FACES: [&[usize]; 20] = [
+    &[0, 4, 8], &[2, 4, 0], &[10, 6, 0], &[0, 6, 2], &[0, 8, 10],
+    &[8, 5, 1], &[1, 5, 3], &[1, 7, 10], &[3, 7, 1], &[10, 8, 1],
+    &[9, 4, 2], &[2, 6, 11], &[11, 9, 2], &[3, 5, 9], &[11, 7, 3],
+    &[3, 9, 11], &[4, 5, 8], &[9, 5, 4], &[10, 7, 6], &[6, 7, 11],
+];
+
+// Define a simple wireframe sphere
+const SPHERE_LATS: usize = 10;
+const SPHERE_LONGS: usize = 20;
+
+/// A 4x4 matrix used for the camera and model transforms, operating on
+/// column vectors (`m * v`), so transforms compose right-to-left: `a.mul(&b)`
+/// applies `b` to a point first, then `a`.
+#[derive(Clone, Copy)]
+struct Mat4 {
+    m: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    fn zero() -> Self {
+        Mat4 { m: [[0.0; 4]; 4] }
+    }
+
+    fn identity() -> Self {
+        let mut m = Mat4::zero();
+        for i in 0..4 {
+            m.m[i][i] = 1.0;
+        }
+        m
+    }
+
+    fn perspective(fovy: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fovy / 2.0).tan();
+        let mut m = Mat4::zero();
+        m.m[0][0] = f / aspect;
+        m.m[1][1] = f;
+        m.m[2][2] = (far + near) / (near - far);
+        m.m[2][3] = 2.0 * far * near / (near - far);
+        m.m[3][2] = -1.0;
+        m
+    }
+
+    fn look_at(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> Self {
+        let f = normalize([target[0] - eye[0], target[1] - eye[1], target[2] - eye[2]]);
+        let s = normalize(cross(f, up));
+        let u = cross(s, f);
+        let mut m = Mat4::identity();
+        m.m[0] = [s[0], s[1], s[2], -dot_product(s, eye)];
+        m.m[1] = [u[0], u[1], u[2], -dot_product(u, eye)];
+        m.m[2] = [-f[0], -f[1], -f[2], dot_product(f, eye)];
+        m
+    }
+
+    fn translate(t: [f32; 3]) -> Self {
+        let mut m = Mat4::identity();
+        m.m[0][3] = t[0];
+        m.m[1][3] = t[1];
+        m.m[2][3] = t[2];
+        m
+    }
+
+    fn scale(s: f32) -> Self {
+        let mut m = Mat4::identity();
+        m.m[0][0] = s;
+        m.m[1][1] = s;
+        m.m[2][2] = s;
+        m
+    }
+
+    /// Rotation by `angle` radians about an arbitrary (not necessarily
+    /// normalized) `axis`, via the Rodrigues rotation formula.
+    fn rotate(angle: f32, axis: [f32; 3]) -> Self {
+        let a = normalize(axis);
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        let (x, y, z) = (a[0], a[1], a[2]);
+        let mut m = Mat4::identity();
+        m.m[0] = [t * x * x + c, t * x * y - s * z, t * x * z + s * y, 0.0];
+        m.m[1] = [t * x * y + s * z, t * y * y + c, t * y * z - s * x, 0.0];
+        m.m[2] = [t * x * z - s * y, t * y * z + s * x, t * z * z + c, 0.0];
+        m
+    }
+
+    fn mul(&self, other: &Mat4) -> Mat4 {
+        let mut r = Mat4::zero();
+        for i in 0..4 {
+            for j in 0..4 {
+                r.m[i][j] = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        r
+    }
+
+    /// Transforms a point through this matrix as homogeneous coordinates
+    /// `(x, y, z, 1)` and applies the perspective divide.
+    fn mul_vec3(&self, v: [f32; 3]) -> [f32; 3] {
+        let m = &self.m;
+        let cx = m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2] + m[0][3];
+        let cy = m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2] + m[1][3];
+        let cz = m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2] + m[2][3];
+        let cw = m[3][0] * v[0] + m[3][1] * v[1] + m[3][2] * v[2] + m[3][3];
+        let w = if cw.abs() < 1e-6 { 1e-6 } else { cw };
+        [cx / w, cy / w, cz / w]
+    }
+}
+
+/// Selects between the edge-rasterizing wireframe renderer, the SDF
+/// raymarcher, and the triangle-mesh ray tracer, chosen via a CLI flag in
+/// `main`.
+#[derive(Clone, Copy, PartialEq)]
+enum RenderMode {
+    Wireframe,
+    Raymarch,
+    Raytrace,
+}
+
+fn parse_render_mode() -> RenderMode {
+    match std::env::args().nth(1).as_deref() {
+        Some("--raymarch") | Some("-r") => RenderMode::Raymarch,
+        Some("--raytrace") | Some("-t") => RenderMode::Raytrace,
+        _ => RenderMode::Wireframe,
+    }
+}
+
+struct Solid {
+    vertices: Vec<[f32; 3]>,
+    faces: &'static [&'static [usize]],
+    scale: f32,
+    sv_ratio: f32,
+    angle: f32,
+    axis: [f32; 3],
+    position: [f32; 3],
+    velocity: [f32; 3],
+    radius: f32,
+}
+
+impl Solid {
+    fn new(
+        vertices: &'static [[f32; 3]],
+        faces: &'static [&'static [usize]],
+        scale: f32,
+        sv_ratio: f32,
+        axis: [f32; 3],
+        position: [f32; 3],
+        velocity: [f32; 3],
+    ) -> Self {
+        let scaled_vertices: Vec<[f32; 3]> =
+            vertices.iter().map(|&v| [v[0] * scale, v[1] * scale, v[2] * scale]).collect();
+        let radius = scaled_vertices
+            .iter()
+            .map(|&v| dot_product(v, v).sqrt())
+            .fold(0.0, f32::max);
+        Solid {
+            vertices: scaled_vertices,
+            faces,
+            scale,
+            sv_ratio,
+            angle: 0.0,
+            axis: normalize(axis),
+            position,
+            velocity,
+            radius,
+        }
+    }
+
+    fn update(&mut self, omega: f32) {
+        self.angle += omega;
+    }
+}
+
+/// A solid's placement for one frame, used by the raymarcher to transform a
+/// world-space point into the solid's local (unrotated, untranslated) space.
+struct SolidFrame<'a> {
+    solid: &'a Solid,
+    inv_rotation: Mat4,
+}
+
+impl<'a> SolidFrame<'a> {
+    fn new(solid: &'a Solid) -> Self {
+        SolidFrame {
+            solid,
+            inv_rotation: Mat4::rotate(-solid.angle, solid.axis),
+        }
+    }
+
+    /// Signed distance from `p` to this convex solid, expressed as the max
+    /// of the signed half-space distances over its face planes.
+    fn sdf(&self, p: [f32; 3]) -> f32 {
+        let local = self.inv_rotation.mul_vec3([
+            p[0] - self.solid.position[0],
+            p[1] - self.solid.position[1],
+            p[2] - self.solid.position[2],
+        ]);
+        let mut d = f32::MIN;
+        for face in self.solid.faces.iter() {
+            let v0 = self.solid.vertices[face[0]];
+            let v1 = self.solid.vertices[face[1]];
+            let v2 = self.solid.vertices[face[2]];
+            let n = normalize(cross(
+                [v1[0] - v0[0], v1[1] - v0[1], v1[2] - v0[2]],
+                [v2[0] - v0[0], v2[1] - v0[1], v2[2] - v0[2]],
+            ));
+            let c = dot_product(n, v0);
+            let dist = dot_product(local, n) - c;
+            if dist > d {
+                d = dist;
+            }
+        }
+        d
+    }
+}
+
+/// Signed distance to the whole scene: the central sphere unioned with every
+/// orbiting solid.
+fn scene_sdf(p: [f32; 3], frames: &[SolidFrame]) -> f32 {
+    let mut d = dot_product(p, p).sqrt() - SPHERE_RADIUS;
+    for frame in frames {
+        d = d.min(frame.sdf(p));
+    }
+    d
+}
+
+/// Surface normal at `p` via central differences of the scene SDF.
+fn scene_normal(p: [f32; 3], frames: &[SolidFrame]) -> [f32; 3] {
+    let e = 1e-3;
+    let dx = scene_sdf([p[0] + e, p[1], p[2]], frames) - scene_sdf([p[0] - e, p[1], p[2]], frames);
+    let dy = scene_sdf([p[0], p[1] + e, p[2]], frames) - scene_sdf([p[0], p[1] - e, p[2]], frames);
+    let dz = scene_sdf([p[0], p[1], p[2] + e], frames) - scene_sdf([p[0], p[1], p[2] - e], frames);
+    normalize([dx, dy, dz])
+}
+
+/// Soft shadow term: marches toward the light, tracking the tightest cone
+/// that still clears the scene to approximate penumbra (Quilez's trick).
+fn soft_shadow(p: [f32; 3], light_dir: [f32; 3], frames: &[SolidFrame]) -> f32 {
+    let light = normalize(light_dir);
+    let mut res: f32 = 1.0;
+    let mut t = 0.02;
+    while t < SHADOW_MAX_DIST {
+        let pt = [p[0] + light[0] * t, p[1] + light[1] * t, p[2] + light[2] * t];
+        let d = scene_sdf(pt, frames);
+        if d < MARCH_HIT_EPS {
+            return 0.0;
+        }
+        res = res.min(SHADOW_SOFTNESS * d / t);
+        t += d;
+    }
+    res.max(0.0).min(1.0)
+}
+
+/// Cheap ambient occlusion: samples the SDF a few fixed steps out along the
+/// normal and penalizes how much closer the surface is than free space.
+fn ambient_occlusion(p: [f32; 3], normal: [f32; 3], frames: &[SolidFrame]) -> f32 {
+    let mut occlusion = 0.0;
+    let mut weight = 1.0;
+    for i in 1..=5 {
+        let h = 0.02 * i as f32;
+        let sample = [p[0] + normal[0] * h, p[1] + normal[1] * h, p[2] + normal[2] * h];
+        let d = scene_sdf(sample, frames);
+        occlusion += (h - d) * weight;
+        weight *= 0.8;
+    }
+    (1.0 - 3.0 * occlusion).max(0.0).min(1.0)
+}
+
+/// Casts a ray into the scene and returns the shaded intensity at the first
+/// hit (0.0 on a miss), combining diffuse light, soft shadow, and AO.
+fn raymarch(ro: [f32; 3], rd: [f32; 3], frames: &[SolidFrame], light_dir: [f32; 3]) -> f32 {
+    let mut t = 0.0;
+    for _ in 0..MARCH_MAX_STEPS {
+        let p = [ro[0] + rd[0] * t, ro[1] + rd[1] * t, ro[2] + rd[2] * t];
+        let d = scene_sdf(p, frames);
+        if d < MARCH_HIT_EPS {
+            let normal = scene_normal(p, frames);
+            let diffuse = dot_product(normal, normalize(light_dir)).max(0.0);
+            let shadow = soft_shadow(p, light_dir, frames);
+            let occlusion = ambient_occlusion(p, normal, frames);
+            return (diffuse * shadow * occlusion).max(0.0).min(1.0);
+        }
+        t += d;
+        if t > MARCH_MAX_DIST {
+            break;
+        }
+    }
+    0.0
+}
+
+/// The camera's world-space right/up/forward basis, read off the rows of its
+/// view matrix so primary rays can be built without re-deriving `look_at`.
+fn camera_basis(view: &Mat4) -> ([f32; 3], [f32; 3], [f32; 3]) {
+    let right = [view.m[0][0], view.m[0][1], view.m[0][2]];
+    let up = [view.m[1][0], view.m[1][1], view.m[1][2]];
+    let forward = [-view.m[2][0], -view.m[2][1], -view.m[2][2]];
+    (right, up, forward)
+}
+
+/// Builds the primary ray for screen cell `(x, y)` from the camera basis and
+/// half-FOV tangent, shared by the raymarch and ray-trace renderers.
+fn primary_ray(x: usize, y: usize, right: [f32; 3], up: [f32; 3], forward: [f32; 3], tan_half_fovy: f32) -> [f32; 3] {
+    let ndc_y = ((y as f32 + 0.5) / HEIGHT as f32) * 2.0 - 1.0;
+    let ndc_x = ((x as f32 + 0.5) / WIDTH as f32) * 2.0 - 1.0;
+    let dir_y = ndc_y * tan_half_fovy;
+    let dir_x = ndc_x * tan_half_fovy * CHAR_ASPECT;
+    normalize([
+        right[0] * dir_x + up[0] * dir_y + forward[0],
+        right[1] * dir_x + up[1] * dir_y + forward[1],
+        right[2] * dir_x + up[2] * dir_y + forward[2],
+    ])
+}
+
+/// Renders one frame by raymarching the scene SDF, cell by cell.
+fn render_raymarch(screen: &mut Vec<Vec<char>>, frames: &[SolidFrame], view: &Mat4, light_dir: [f32; 3]) {
+    let (right, up, forward) = camera_basis(view);
+    let tan_half_fovy = (FOVY / 2.0).tan();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let rd = primary_ray(x, y, right, up, forward, tan_half_fovy);
+            let intensity = raymarch(CAMERA_EYE, rd, frames, light_dir);
+            screen[y][x] = ramp_char(intensity);
+        }
+    }
+}
+
+/// A single world-space triangle, fan-triangulated out of a solid's face.
+struct Triangle {
+    v0: [f32; 3],
+    v1: [f32; 3],
+    v2: [f32; 3],
+}
+
+/// Transforms every solid's vertices into world space for the current frame
+/// and fan-triangulates each face (vertex 0, i, i+1) into a flat triangle list.
+fn build_triangles(solids: &[Solid]) -> Vec<Triangle> {
+    let mut triangles = Vec::new();
+    for solid in solids {
+        let model = Mat4::translate(solid.position).mul(&Mat4::rotate(solid.angle, solid.axis));
+        let world: Vec<[f32; 3]> = solid.vertices.iter().map(|&v| model.mul_vec3(v)).collect();
+        for face in solid.faces.iter() {
+            for i in 1..face.len() - 1 {
+                triangles.push(Triangle { v0: world[face[0]], v1: world[face[i]], v2: world[face[i + 1]] });
+            }
+        }
+    }
+    triangles
+}
+
+/// Möller–Trumbore ray/triangle intersection. Treats the triangle as
+/// two-sided (only `|det|` is checked, not its sign) and returns the hit
+/// distance along `rd` if the ray crosses the triangle's interior ahead of
+/// the origin.
+fn intersect_triangle(ro: [f32; 3], rd: [f32; 3], tri: &Triangle) -> Option<f32> {
+    const EPS: f32 = 1e-6;
+    let e1 = sub(tri.v1, tri.v0);
+    let e2 = sub(tri.v2, tri.v0);
+    let pvec = cross(rd, e2);
+    let det = dot_product(e1, pvec);
+    if det.abs() < EPS {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let tvec = sub(ro, tri.v0);
+    let u = dot_product(tvec, pvec) * inv_det;
+    if u < 0.0 || u > 1.0 {
+        return None;
+    }
+    let qvec = cross(tvec, e1);
+    let v = dot_product(rd, qvec) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = dot_product(e2, qvec) * inv_det;
+    if t > EPS { Some(t) } else { None }
+}
+
+/// Ray/sphere intersection against the central sphere (centered on the
+/// origin, radius `SPHERE_RADIUS`), so the ray-traced scene matches the
+/// sphere every other render mode draws.
+fn intersect_sphere(ro: [f32; 3], rd: [f32; 3]) -> Option<f32> {
+    const EPS: f32 = 1e-4;
+    let b = dot_product(ro, rd);
+    let c = dot_product(ro, ro) - SPHERE_RADIUS * SPHERE_RADIUS;
+    let disc = b * b - c;
+    if disc < 0.0 {
+        return None;
+    }
+    let sqrt_disc = disc.sqrt();
+    let t0 = -b - sqrt_disc;
+    if t0 > EPS {
+        return Some(t0);
+    }
+    let t1 = -b + sqrt_disc;
+    if t1 > EPS { Some(t1) } else { None }
+}
+
+/// Finds the nearest hit in the scene (the central sphere plus every
+/// triangle), returning the hit distance and a normal flipped to face back
+/// toward the ray origin.
+fn nearest_scene_hit(ro: [f32; 3], rd: [f32; 3], triangles: &[Triangle]) -> Option<(f32, [f32; 3])> {
+    let mut closest = intersect_sphere(ro, rd)
+        .map(|t| (t, normalize([ro[0] + rd[0] * t, ro[1] + rd[1] * t, ro[2] + rd[2] * t])));
+    for tri in triangles {
+        if let Some(t) = intersect_triangle(ro, rd, tri) {
+            if closest.map_or(true, |(closest_t, _)| t < closest_t) {
+                let mut normal = normalize(cross(sub(tri.v1, tri.v0), sub(tri.v2, tri.v0)));
+                if dot_product(normal, rd) > 0.0 {
+                    normal = [-normal[0], -normal[1], -normal[2]];
+                }
+                closest = Some((t, normal));
+            }
+        }
+    }
+    closest
+}
+
+/// Casts a second ray from `p` toward the light and reports whether the
+/// sphere or any triangle blocks it, for a hard (binary) shadow term.
+fn in_shadow(p: [f32; 3], light_dir: [f32; 3], triangles: &[Triangle]) -> bool {
+    let light = normalize(light_dir);
+    const BIAS: f32 = 1e-3;
+    let ro = [p[0] + light[0] * BIAS, p[1] + light[1] * BIAS, p[2] + light[2] * BIAS];
+    intersect_sphere(ro, light).is_some() || triangles.iter().any(|tri| intersect_triangle(ro, light, tri).is_some())
+}
+
+/// Renders one frame by ray tracing the central sphere and the solids'
+/// triangulated meshes, cell by cell, with hard shadows.
+fn render_raytrace(screen: &mut Vec<Vec<char>>, triangles: &[Triangle], view: &Mat4, light_dir: [f32; 3]) {
+    let (right, up, forward) = camera_basis(view);
+    let tan_half_fovy = (FOVY / 2.0).tan();
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let rd = primary_ray(x, y, right, up, forward, tan_half_fovy);
+            let intensity = match nearest_scene_hit(CAMERA_EYE, rd, triangles) {
+                Some((t, normal)) => {
+                    let p = [CAMERA_EYE[0] + rd[0] * t, CAMERA_EYE[1] + rd[1] * t, CAMERA_EYE[2] + rd[2] * t];
+                    let diffuse = dot_product(normal, normalize(light_dir)).max(0.0);
+                    let shadow = if in_shadow(p, light_dir, triangles) { 0.0 } else { 1.0 };
+                    diffuse * shadow
+                }
+                None => 0.0,
+            };
+            screen[y][x] = ramp_char(intensity);
+        }
+    }
+}
+
+fn main() {
+    let mode = parse_render_mode();
+    let scales = [0.234, 0.286, 0.606, 0.539, 1.0];
+    let sv_ratios = [14.697, 6.0, 7.348, 3.013, 4.899];
+    // Each solid tumbles about its own arbitrary axis rather than a shared Y spin.
+    let axes = [
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [1.0, -1.0, 1.0],
+    ];
+
+    // Seed each solid at a distinct phase around the old shared ellipse, with
+    // a tangential starting velocity, so they begin spread out instead of
+    // stacked on top of one another.
+    let start = |phase: f32| -> ([f32; 3], [f32; 3]) {
+        let position = [
+            ORBIT_A * phase.cos(),
+            ORBIT_B * phase.sin(),
+            ORBIT_C * (phase + std::f32::consts::PI / 2.0).sin(),
+        ];
+        let velocity = [
+            -ORBIT_A * phase.sin() * ORBIT_SPEED,
+            ORBIT_B * phase.cos() * ORBIT_SPEED,
+            ORBIT_C * (phase + std::f32::consts::PI / 2.0).cos() * ORBIT_SPEED,
+        ];
+        (position, velocity)
+    };
+    let phases = [0.0, 1.257, 2.513, 3.770, 5.027]; // 2*pi/5 apart
+    let (pos0, vel0) = start(phases[0]);
+    let (pos1, vel1) = start(phases[1]);
+    let (pos2, vel2) = start(phases[2]);
+    let (pos3, vel3) = start(phases[3]);
+    let (pos4, vel4) = start(phases[4]);
+
+    let mut solids = vec![
+        Solid::new(&TETRAHEDRON_VERTS, &TETRAHEDRON_FACES, scales[0], sv_ratios[0], axes[0], pos0, vel0),
+        Solid::new(&CUBE_VERTS, &CUBE_FACES, scales[1], sv_ratios[1], axes[1], pos1, vel1),
+        Solid::new(&OCTAHEDRON_VERTS, &OCTAHEDRON_FACES, scales[2], sv_ratios[2], axes[2], pos2, vel2),
+        Solid::new(&DODECAHEDRON_VERTS, &DODECAHEDRON_FACES, scales[3], sv_ratios[3], axes[3], pos3, vel3),
+        Solid::new(&ICOSAHEDRON_VERTS, &ICOSAHEDRON_FACES, scales[4], sv_ratios[4], axes[4], pos4, vel4),
+    ];
+
+    let mut screen = vec![vec![' '; WIDTH]; HEIGHT];
+    let mut last_screen = vec![vec![' '; WIDTH]; HEIGHT];
+    let mut depth = vec![vec![f32::MAX; WIDTH]; HEIGHT];
+    let frame_time = Duration::from_millis(1000 / TARGET_FPS);
+    let light_dir = [1.0, 1.0, 1.0];
+
+    // Sphere setup
+    let mut sphere_vertices = Vec::new();
+    for i in 0..SPHERE_LATS {
+        let lat = std::f32::consts::PI * i as f32 / (SPHERE_LATS - 1) as f32 - std::f32::consts::PI / 2.0;
+        for j in 0..SPHERE_LONGS {
+            let lon = 2.0 * std::f32::consts::PI * j as f32 / SPHERE_LONGS as f32;
+            let x = SPHERE_RADIUS * lat.cos() * lon.cos();
+            let y = SPHERE_RADIUS * lat.sin();
+            let z = SPHERE_RADIUS * lat.cos() * lon.sin();
+            sphere_vertices.push([x, y, z]);
+        }
+    }
+    let mut sphere_edges = Vec::new();
+    for i in 0..SPHERE_LATS {
+        for j in 0..SPHERE_LONGS {
+            let idx = i * SPHERE_LONGS + j;
+            if i < SPHERE_LATS - 1 {
+                sphere_edges.push((idx, idx + SPHERE_LONGS));
+            }
+            let next_j = (j + 1) % SPHERE_LONGS;
+            sphere_edges.push((idx, i * SPHERE_LONGS + next_j));
+        }
+    }
+
+    print!("\x1B[2J\x1B[1;1H"); // Clear screen and move to top-left corner
+    stdout().flush().unwrap();
+
+    let mut last_frame = Instant::now();
+
+    let proj = Mat4::perspective(FOVY, CHAR_ASPECT, NEAR, FAR);
+    let view = Mat4::look_at(CAMERA_EYE, CAMERA_TARGET, CAMERA_UP);
+    let view_proj = proj.mul(&view);
+
+    loop {
+        let now = Instant::now();
+        if now.duration_since(last_frame) >= frame_time {
+            depth.iter_mut().for_each(|row| row.fill(f32::MAX));
+            screen.iter_mut().for_each(|row| row.fill(' '));
+
+            for solid in solids.iter_mut() {
+                solid.update(BASE_SPEED * solid.sv_ratio);
+                solid.position[0] += solid.velocity[0];
+                solid.position[1] += solid.velocity[1];
+                solid.position[2] += solid.velocity[2];
+            }
+            resolve_collisions(&mut solids);
+            bounce_off_bounds(&mut solids);
+
+            match mode {
+                RenderMode::Wireframe => {
+                    // Render the central sphere (stationary at the origin)
+                    let mut sphere_projected = vec![[0; 2]; sphere_vertices.len()];
+                    let mut sphere_depths = vec![0.0; sphere_vertices.len()];
+                    for (i, vertex) in sphere_vertices.iter().enumerate() {
+                        let clip = view_proj.mul_vec3(*vertex);
+                        sphere_depths[i] = clip[2];
+                        sphere_projected[i] = screen_coords(clip);
+                    }
+                    for &(v1, v2) in sphere_edges.iter() {
+                        let p1 = sphere_projected[v1];
+                        let p2 = sphere_projected[v2];
+                        let avg_depth = (sphere_depths[v1] + sphere_depths[v2]) / 2.0;
+                        let dx = sphere_vertices[v2][0] - sphere_vertices[v1][0];
+                        let dy = sphere_vertices[v2][1] - sphere_vertices[v1][1];
+                        let dz = sphere_vertices[v2][2] - sphere_vertices[v1][2];
+                        let normal = [dx, dy, dz];
+                        let light = dot_product(normalize(normal), normalize(light_dir));
+                        let intensity = (light * 0.5 + 0.5).max(0.0).min(1.0);
+                        draw_line(&mut screen, &mut depth, p1[0], p1[1], p2[0], p2[1], avg_depth, intensity);
+                    }
+
+                    // Render the nested solids, each with its own billiard position
+                    // and arbitrary-axis spin composed into a model matrix.
+                    for solid in solids.iter() {
+                        let model = Mat4::translate(solid.position).mul(&Mat4::rotate(solid.angle, solid.axis));
+                        let mvp = view_proj.mul(&model);
+
+                        let mut projected = vec![[0; 2]; solid.vertices.len()];
+                        let mut depths = vec![0.0; solid.vertices.len()];
+                        for (i, vertex) in solid.vertices.iter().enumerate() {
+                            let clip = mvp.mul_vec3(*vertex);
+                            depths[i] = clip[2];
+                            projected[i] = screen_coords(clip);
+                        }
+
+                        let rotation = Mat4::rotate(solid.angle, solid.axis);
+                        for face in solid.faces.iter() {
+                            let v0 = solid.vertices[face[0]];
+                            let v1 = solid.vertices[face[1]];
+                            let v2 = solid.vertices[face[2]];
+                            let local_normal = normalize(cross(
+                                [v1[0] - v0[0], v1[1] - v0[1], v1[2] - v0[2]],
+                                [v2[0] - v0[0], v2[1] - v0[1], v2[2] - v0[2]],
+                            ));
+                            let normal = rotation.mul_vec3(local_normal);
+                            if dot_product(normal, VIEW_DIR) > 0.0 {
+                                continue; // back-facing: culled
+                            }
+                            let light = dot_product(normal, normalize(light_dir)).max(0.0);
+                            for i in 0..face.len() {
+                                let a = face[i];
+                                let b = face[(i + 1) % face.len()];
+                                let p1 = projected[a];
+                                let p2 = projected[b];
+                                let avg_depth = (depths[a] + depths[b]) / 2.0;
+                                draw_line(&mut screen, &mut depth, p1[0], p1[1], p2[0], p2[1], avg_depth, light);
+                            }
+                        }
+                    }
+                }
+                RenderMode::Raymarch => {
+                    let frames: Vec<SolidFrame> = solids.iter().map(SolidFrame::new).collect();
+                    render_raymarch(&mut screen, &frames, &view, light_dir);
+                }
+                RenderMode::Raytrace => {
+                    let triangles = build_triangles(&solids);
+                    render_raytrace(&mut screen, &triangles, &view, light_dir);
+                }
+            }
+
+            update_screen(&screen, &last_screen);
+            last_screen.clone_from(&screen);
+            stdout().flush().unwrap();
+
+            last_frame = now;
+        }
+        std::thread::sleep(frame_time - now.duration_since(last_frame));
+    }
+}
+
+/// Tests every pair of solids' bounding spheres and, on overlap, separates
+/// them and reflects their relative velocity along the contact normal with
+/// restitution `RESTITUTION` (equal-mass collision).
+fn resolve_collisions(solids: &mut [Solid]) {
+    for i in 0..solids.len() {
+        for j in (i + 1)..solids.len() {
+            let (left, right) = solids.split_at_mut(j);
+            let a = &mut left[i];
+            let b = &mut right[0];
+
+            let delta = [
+                b.position[0] - a.position[0],
+                b.position[1] - a.position[1],
+                b.position[2] - a.position[2],
+            ];
+            let dist = dot_product(delta, delta).sqrt();
+            let min_dist = a.radius + b.radius;
+            if dist >= min_dist || dist < 1e-6 {
+                continue;
+            }
+            let n = [delta[0] / dist, delta[1] / dist, delta[2] / dist];
+
+            // Push the bodies apart so they no longer overlap.
+            let overlap = min_dist - dist;
+            for k in 0..3 {
+                a.position[k] -= n[k] * overlap * 0.5;
+                b.position[k] += n[k] * overlap * 0.5;
+            }
+
+            // Equal-mass collision: split the relative-velocity
+            // impulse along the contact normal between both bodies.
+            let relative_velocity = [
+                a.velocity[0] - b.velocity[0],
+                a.velocity[1] - b.velocity[1],
+                a.velocity[2] - b.velocity[2],
+            ];
+            // n points from a to b, so a positive approach_speed means the
+            // pair is closing (distance shrinking); skip pairs already
+            // separating so the impulse only fires on real collisions.
+            let approach_speed = dot_product(relative_velocity, n);
+            if approach_speed > 0.0 {
+                let impulse = (1.0 + RESTITUTION) * approach_speed * 0.5;
+                for k in 0..3 {
+                    a.velocity[k] -= n[k] * impulse;
+                    b.velocity[k] += n[k] * impulse;
+                }
+            }
+        }
+    }
+}
+
+/// Bounces each solid's velocity off the axis-aligned box bounding the
+/// visible volume, clamping its position back inside on overlap.
+fn bounce_off_bounds(solids: &mut [Solid]) {
+    for solid in solids.iter_mut() {
+        for k in 0..3 {
+            let limit = BOUNDS[k] - solid.radius;
+            if solid.position[k] > limit {
+                solid.position[k] = limit;
+                solid.velocity[k] = -solid.velocity[k].abs() * RESTITUTION;
+            } else if solid.position[k] < -limit {
+                solid.position[k] = -limit;
+                solid.velocity[k] = solid.velocity[k].abs() * RESTITUTION;
+            }
+        }
+    }
+}
+
+/// Maps a perspective-divided clip-space point to integer screen coordinates.
+fn screen_coords(clip: [f32; 3]) -> [i32; 2] {
+    let px = ((clip[0] * 0.5 + 0.5) * WIDTH as f32).round() as i32;
+    let py = ((clip[1] * 0.5 + 0.5) * HEIGHT as f32).round() as i32;
+    [px, py]
+}
+
+fn update_screen(screen: &Vec<Vec<char>>, last_screen: &Vec<Vec<char>>) {
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            if screen[y][x] != last_screen[y][x] {
+                print!("\x1B[{};{}H{}", y + 1, x + 1, screen[y][x]);
+            }
+        }
+    }
+}
+
+fn draw_line(screen: &mut Vec<Vec<char>>, depth: &mut Vec<Vec<f32>>,
+             x0: i32, y0: i32, x1: i32, y1: i32, z: f32, intensity: f32) {
+    let dx = (x1 - x0).abs();
+    let dy = (y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx - dy;
+
+    let char = ramp_char(intensity);
+
+    let mut x = x0;
+    let mut y = y0;
+    loop {
+        let wrapped_x = (x + WIDTH as i32) % WIDTH as i32;
+        let wrapped_y = (y + HEIGHT as i32) % HEIGHT as i32;
+        if wrapped_x >= 0 && wrapped_x < WIDTH as i32 && wrapped_y >= 0 && wrapped_y < HEIGHT as i32 {
+            let idx_x = wrapped_x as usize;
+            let idx_y = wrapped_y as usize;
+            if z < depth[idx_y][idx_x] {
+                depth[idx_y][idx_x] = z;
+                screen[idx_y][idx_x] = char;
+            }
+        }
+        if x == x1 && y == y1 { break; }
+        let e2 = 2 * err;
+        if e2 > -dy { err -= dy; x += sx; }
+        if e2 < dx { err += dx; y += sy; }
+    }
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len == 0.0 { [0.0, 0.0, 0.0] } else { [v[0] / len, v[1] / len, v[2] / len] }
+}
+
+fn dot_product(v1: [f32; 3], v2: [f32; 3]) -> f32 {
+    v1[0] * v2[0] + v1[1] * v2[1] + v1[2] * v2[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draw_line_keeps_nearest_sample() {
+        let mut screen = vec![vec![' '; 4]; 4];
+        let mut depth = vec![vec![f32::MAX; 4]; 4];
+
+        // Nearest sample (small NDC z) drawn first: should land.
+        draw_line(&mut screen, &mut depth, 1, 1, 1, 1, 0.5, 1.0);
+        assert_eq!(screen[1][1], ramp_char(1.0));
+
+        // A farther sample (larger NDC z) at the same pixel must not
+        // overwrite the nearer one already there.
+        draw_line(&mut screen, &mut depth, 1, 1, 1, 1, 0.9, 0.0);
+        assert_eq!(screen[1][1], ramp_char(1.0));
+
+        // A nearer sample still beats whatever is currently in the buffer.
+        draw_line(&mut screen, &mut depth, 1, 1, 1, 1, 0.1, 0.5);
+        assert_eq!(screen[1][1], ramp_char(0.5));
+    }
+
+    #[test]
+    fn resolve_collisions_bounces_closing_pair() {
+        let a = Solid::new(&TETRAHEDRON_VERTS, &TETRAHEDRON_FACES, 1.0, 1.0, [0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+        let b = Solid::new(&TETRAHEDRON_VERTS, &TETRAHEDRON_FACES, 1.0, 1.0, [0.0, 1.0, 0.0], [0.1, 0.0, 0.0], [-1.0, 0.0, 0.0]);
+        let mut solids = vec![a, b];
+        resolve_collisions(&mut solids);
+
+        // The pair was closing (a moving toward b, b moving toward a), so
+        // the impulse must fire and reverse their approach: a pushed back
+        // toward -x, b pushed back toward +x.
+        assert!(solids[0].velocity[0] < 0.0);
+        assert!(solids[1].velocity[0] > 0.0);
+    }
+}